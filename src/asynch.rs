@@ -0,0 +1,1127 @@
+//! Async mirrors of [`Persist`](crate::Persist) and
+//! [`PersistentStorage`](crate::PersistentStorage).
+//!
+//! The rest of this crate targets the `embedded-io 0.4` line (the
+//! `blocking` module and its minimal `Io`/`Error` traits). There is no
+//! `embedded-io-async` release compatible with that line — it starts at
+//! 0.6, which pairs with an `embedded-io` major that drops `blocking` and
+//! `Io` entirely. Rather than split the crate across two incompatible
+//! `embedded-io` generations, the async `Read`/`Write`/`Seek` traits below
+//! are defined locally, mirroring `embedded_io::blocking`'s shape but with
+//! `async fn` methods, and sharing the same `embedded_io::Io` error
+//! contract as the blocking side.
+//!
+//! This is a deliberate deviation from binding directly to
+//! `embedded_io_async::{Read, Write, Seek}`: doing so would require
+//! bumping the whole crate off `embedded-io 0.4` and onto the 0.6/0.7
+//! line, losing `blocking`/`Io` for every other module in the process.
+//! Until that migration is worth doing on its own, these local traits are
+//! the intended shape of the async surface, not a placeholder.
+
+use crate::PersistentStorage;
+use core::fmt::Debug;
+
+/// Async counterpart of `embedded_io::blocking::Read`.
+#[allow(async_fn_in_trait)]
+pub trait Read: embedded_io::Io {
+    /// Reads into `buf`, returning the number of bytes read.
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// Async counterpart of `embedded_io::blocking::Write`.
+#[allow(async_fn_in_trait)]
+pub trait Write: embedded_io::Io {
+    /// Writes from `buf`, returning the number of bytes written.
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error>;
+
+    /// Flushes any buffered data to the underlying sink.
+    async fn flush(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Async counterpart of `embedded_io::blocking::Seek`.
+#[allow(async_fn_in_trait)]
+pub trait Seek: embedded_io::Io {
+    /// Seeks to `pos`, returning the new absolute position.
+    async fn seek(&mut self, pos: embedded_io::SeekFrom) -> Result<u64, Self::Error>;
+}
+
+/// Async counterpart of [`Persist`](crate::Persist).
+// `async fn` in a public trait is allowed here for the same reason the
+// `Read`/`Write`/`Seek` traits above take it: it reads far closer to the
+// blocking original than spelling every method as `-> impl Future`.
+#[allow(async_fn_in_trait)]
+pub trait AsyncPersist<Io>: Sized
+where
+    Io: Read + Write,
+{
+    /// Associated error type.
+    type Error: Debug;
+
+    /// Persists `self` to `sink`.
+    async fn persist(&mut self, sink: Io) -> Result<(), Self::Error>;
+
+    /// Loads `Self` from `source`.
+    async fn load(source: Io) -> Result<Self, Self::Error>;
+}
+
+/// Async counterpart of [`PersistentStorage`].
+#[allow(async_fn_in_trait)]
+pub trait AsyncPersistentStorage {
+    /// The identifier for an object.
+    type Id;
+    /// Flags for creation.
+    type Flags;
+    /// Contains object information.
+    type Info;
+    /// Associated error type.
+    type Error: Debug;
+    /// The produced `Io` type.
+    type Io<'a>: Read + Write + Seek
+    where
+        Self: 'a;
+
+    /// Creates a new object.
+    async fn create(&mut self, objid: &Self::Id, flags: &Self::Flags) -> Result<(), Self::Error>;
+
+    /// Destroys an object.
+    async fn destroy(&mut self, objid: &Self::Id) -> Result<(), Self::Error>;
+
+    /// Gets information about an object.
+    async fn get_info(&mut self, objid: &Self::Id) -> Result<Self::Info, Self::Error>;
+
+    /// Sets information about an object.
+    async fn set_info(&mut self, objid: &Self::Id, info: Self::Info) -> Result<(), Self::Error>;
+
+    /// Returns an `Io` handle to read object with.
+    async fn read_handle(&mut self, objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error>;
+
+    /// Returns an `Io` handle to write an object with.
+    async fn write_handle(&mut self, objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error>;
+
+    /// Returns an `Io` handle to read and write an object with.
+    async fn rw_handle(&mut self, objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error>;
+
+    /// Shortens an object.
+    async fn truncate(&mut self, objid: &Self::Id, size: u64) -> Result<(), Self::Error>;
+
+    /// Persists state to a fixed place.
+    async fn persist_state(&mut self) -> Result<(), Self::Error>;
+
+    /// Loads state from a fixed place.
+    async fn load_state(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Lifts a blocking [`PersistentStorage`] into [`AsyncPersistentStorage`] by
+/// running every operation inline.
+///
+/// Suitable for targets simple enough that blocking briefly inside an async
+/// context is acceptable; see [`offload::Offloaded`] for a variant that
+/// drives the inner storage from a spawned task instead.
+pub struct Blocking<S>(pub S);
+
+impl<S> Blocking<S> {
+    /// Wraps `inner`.
+    pub fn new(inner: S) -> Self {
+        Self(inner)
+    }
+
+    /// Returns a reference to the wrapped storage.
+    pub fn get_ref(&self) -> &S {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the wrapped storage.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.0
+    }
+
+    /// Unwraps this adapter, returning the wrapped storage.
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+/// Adapts a blocking `embedded_io` stream to this module's async
+/// `Read`/`Write`/`Seek` traits by completing every operation immediately.
+pub struct BlockingIo<Io>(pub Io);
+
+impl<Io: embedded_io::Io> embedded_io::Io for BlockingIo<Io> {
+    type Error = Io::Error;
+}
+
+impl<Io: embedded_io::blocking::Read> Read for BlockingIo<Io> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.0.read(buf)
+    }
+}
+
+impl<Io: embedded_io::blocking::Write> Write for BlockingIo<Io> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.0.write(buf)
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.flush()
+    }
+}
+
+impl<Io: embedded_io::blocking::Seek> Seek for BlockingIo<Io> {
+    async fn seek(&mut self, pos: embedded_io::SeekFrom) -> Result<u64, Self::Error> {
+        self.0.seek(pos)
+    }
+}
+
+impl<S: PersistentStorage> AsyncPersistentStorage for Blocking<S> {
+    type Id = S::Id;
+    type Flags = S::Flags;
+    type Info = S::Info;
+    type Error = S::Error;
+    type Io<'a>
+        = BlockingIo<S::Io<'a>>
+    where
+        Self: 'a;
+
+    async fn create(&mut self, objid: &Self::Id, flags: &Self::Flags) -> Result<(), Self::Error> {
+        self.0.create(objid, flags)
+    }
+
+    async fn destroy(&mut self, objid: &Self::Id) -> Result<(), Self::Error> {
+        self.0.destroy(objid)
+    }
+
+    async fn get_info(&mut self, objid: &Self::Id) -> Result<Self::Info, Self::Error> {
+        self.0.get_info(objid)
+    }
+
+    async fn set_info(&mut self, objid: &Self::Id, info: Self::Info) -> Result<(), Self::Error> {
+        self.0.set_info(objid, info)
+    }
+
+    async fn read_handle(&mut self, objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+        self.0.read_handle(objid).map(BlockingIo)
+    }
+
+    async fn write_handle(&mut self, objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+        self.0.write_handle(objid).map(BlockingIo)
+    }
+
+    async fn rw_handle(&mut self, objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+        self.0.rw_handle(objid).map(BlockingIo)
+    }
+
+    async fn truncate(&mut self, objid: &Self::Id, size: u64) -> Result<(), Self::Error> {
+        self.0.truncate(objid, size)
+    }
+
+    async fn persist_state(&mut self) -> Result<(), Self::Error> {
+        self.0.persist_state()
+    }
+
+    async fn load_state(&mut self) -> Result<(), Self::Error> {
+        self.0.load_state()
+    }
+}
+
+#[cfg(feature = "async-offload")]
+pub mod offload {
+    //! Drives a blocking [`PersistentStorage`](crate::PersistentStorage) from
+    //! a spawned task rather than running it inline.
+
+    use super::*;
+    use embedded_io::blocking::{Read as _, Seek as _, Write as _};
+    use embedded_io::{Error as EioError, ErrorKind, SeekFrom};
+    use std::sync::{Arc, Mutex};
+    use std::task::{Poll, Waker};
+
+    struct Shared<T> {
+        result: Mutex<Option<T>>,
+        waker: Mutex<Option<Waker>>,
+    }
+
+    /// A handle to work running on a spawned thread.
+    struct BlockingTask<T> {
+        shared: Arc<Shared<T>>,
+    }
+
+    impl<T: Send + 'static> core::future::Future for BlockingTask<T> {
+        type Output = T;
+
+        fn poll(
+            self: core::pin::Pin<&mut Self>,
+            cx: &mut core::task::Context<'_>,
+        ) -> Poll<Self::Output> {
+            if let Some(value) = self.shared.result.lock().unwrap().take() {
+                return Poll::Ready(value);
+            }
+            *self.shared.waker.lock().unwrap() = Some(cx.waker().clone());
+            // The background thread may have finished and found no waker
+            // registered between the check above and the line above this
+            // comment; re-check now that one is in place so that race can't
+            // drop the wakeup and park this future forever.
+            if let Some(value) = self.shared.result.lock().unwrap().take() {
+                return Poll::Ready(value);
+            }
+            Poll::Pending
+        }
+    }
+
+    /// Runs `f` on a spawned thread, resolving once it completes.
+    ///
+    /// This mirrors the way async file wrappers (e.g. `tokio::fs::File`)
+    /// drive a synchronous handle under the hood, without depending on any
+    /// particular async runtime.
+    fn spawn_blocking<F, R>(f: F) -> BlockingTask<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let shared = Arc::new(Shared {
+            result: Mutex::new(None),
+            waker: Mutex::new(None),
+        });
+        let shared2 = shared.clone();
+        std::thread::spawn(move || {
+            let result = f();
+            *shared2.result.lock().unwrap() = Some(result);
+            if let Some(waker) = shared2.waker.lock().unwrap().take() {
+                waker.wake();
+            }
+        });
+        BlockingTask { shared }
+    }
+
+    /// Which handle kind an [`OffloadedIo`] was opened as.
+    #[derive(Clone, Copy)]
+    enum Mode {
+        Read,
+        Write,
+        ReadWrite,
+    }
+
+    /// The error returned by an [`OffloadedIo`].
+    #[derive(Debug)]
+    pub enum OffloadedError<E> {
+        /// The underlying storage returned an error.
+        Storage(E),
+        /// The handle's mode does not permit the attempted access.
+        ModeViolation,
+    }
+
+    impl<E: Debug> EioError for OffloadedError<E> {
+        fn kind(&self) -> ErrorKind {
+            // `S::Error` is only required to be `Debug`, not
+            // `embedded_io::Error`, so there is no finer-grained `ErrorKind`
+            // to report here.
+            ErrorKind::Other
+        }
+    }
+
+    /// Offloads a blocking [`PersistentStorage`] onto a spawned thread per
+    /// operation, so async callers never block.
+    pub struct Offloaded<S>(Arc<Mutex<S>>);
+
+    impl<S: PersistentStorage + Send + 'static> Offloaded<S> {
+        /// Wraps `inner`.
+        pub fn new(inner: S) -> Self {
+            Self(Arc::new(Mutex::new(inner)))
+        }
+    }
+
+    impl<S> Clone for Offloaded<S> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    /// An `Io` handle that re-acquires the underlying storage's handle for
+    /// each operation, tracking its own position across calls.
+    pub struct OffloadedIo<S: PersistentStorage> {
+        storage: Arc<Mutex<S>>,
+        objid: S::Id,
+        mode: Mode,
+        pos: u64,
+    }
+
+    impl<S> embedded_io::Io for OffloadedIo<S>
+    where
+        S: PersistentStorage + Send + 'static,
+        S::Id: Clone + Send + 'static,
+        S::Error: Send + 'static,
+    {
+        type Error = OffloadedError<S::Error>;
+    }
+
+    impl<S> Read for OffloadedIo<S>
+    where
+        S: PersistentStorage + Send + 'static,
+        S::Id: Clone + Send + 'static,
+        S::Error: Send + 'static,
+        for<'a> S::Error: From<<S::Io<'a> as embedded_io::Io>::Error>,
+    {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            if !matches!(self.mode, Mode::Read | Mode::ReadWrite) {
+                return Err(OffloadedError::ModeViolation);
+            }
+            let storage = self.storage.clone();
+            let objid = self.objid.clone();
+            let mode = self.mode;
+            let pos = self.pos;
+            let mut owned = vec![0u8; buf.len()];
+            let (n, owned) = spawn_blocking(move || -> Result<(usize, Vec<u8>), S::Error> {
+                let mut guard = storage.lock().unwrap();
+                let mut handle = match mode {
+                    Mode::Read => guard.read_handle(&objid)?,
+                    Mode::ReadWrite => guard.rw_handle(&objid)?,
+                    Mode::Write => unreachable!("write-only handles reject reads before spawning"),
+                };
+                handle.seek(SeekFrom::Start(pos))?;
+                let n = handle.read(&mut owned)?;
+                Ok((n, owned))
+            })
+            .await
+            .map_err(OffloadedError::Storage)?;
+            buf[..n].copy_from_slice(&owned[..n]);
+            self.pos += n as u64;
+            Ok(n)
+        }
+    }
+
+    impl<S> Write for OffloadedIo<S>
+    where
+        S: PersistentStorage + Send + 'static,
+        S::Id: Clone + Send + 'static,
+        S::Error: Send + 'static,
+        for<'a> S::Error: From<<S::Io<'a> as embedded_io::Io>::Error>,
+    {
+        async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            if !matches!(self.mode, Mode::Write | Mode::ReadWrite) {
+                return Err(OffloadedError::ModeViolation);
+            }
+            let storage = self.storage.clone();
+            let objid = self.objid.clone();
+            let mode = self.mode;
+            let pos = self.pos;
+            let owned = buf.to_vec();
+            let n = spawn_blocking(move || -> Result<usize, S::Error> {
+                let mut guard = storage.lock().unwrap();
+                let mut handle = match mode {
+                    Mode::Write => guard.write_handle(&objid)?,
+                    Mode::ReadWrite => guard.rw_handle(&objid)?,
+                    Mode::Read => unreachable!("read-only handles reject writes before spawning"),
+                };
+                handle.seek(SeekFrom::Start(pos))?;
+                let n = handle.write(&owned)?;
+                // This handle is freshly acquired and dropped at the end of
+                // this closure, so if the backend wraps its sink in
+                // something that batches writes (e.g. a `BufWriter`), it
+                // must be flushed here or whatever it buffered is lost
+                // silently when the handle goes out of scope.
+                handle.flush()?;
+                Ok(n)
+            })
+            .await
+            .map_err(OffloadedError::Storage)?;
+            self.pos += n as u64;
+            Ok(n)
+        }
+
+        async fn flush(&mut self) -> Result<(), Self::Error> {
+            if !matches!(self.mode, Mode::Write | Mode::ReadWrite) {
+                return Err(OffloadedError::ModeViolation);
+            }
+            let storage = self.storage.clone();
+            let objid = self.objid.clone();
+            let mode = self.mode;
+            spawn_blocking(move || -> Result<(), S::Error> {
+                let mut guard = storage.lock().unwrap();
+                let mut handle = match mode {
+                    Mode::Write => guard.write_handle(&objid)?,
+                    Mode::ReadWrite => guard.rw_handle(&objid)?,
+                    Mode::Read => unreachable!("read-only handles reject writes before spawning"),
+                };
+                handle.flush()?;
+                Ok(())
+            })
+            .await
+            .map_err(OffloadedError::Storage)
+        }
+    }
+
+    impl<S> Seek for OffloadedIo<S>
+    where
+        S: PersistentStorage + Send + 'static,
+        S::Id: Clone + Send + 'static,
+        S::Error: Send + 'static,
+        for<'a> S::Error: From<<S::Io<'a> as embedded_io::Io>::Error>,
+    {
+        async fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+            self.pos = match pos {
+                SeekFrom::Start(n) => n,
+                SeekFrom::Current(n) => {
+                    if n >= 0 {
+                        self.pos.saturating_add(n as u64)
+                    } else {
+                        self.pos.saturating_sub(n.unsigned_abs())
+                    }
+                }
+                SeekFrom::End(n) => {
+                    let storage = self.storage.clone();
+                    let objid = self.objid.clone();
+                    let mode = self.mode;
+                    spawn_blocking(move || -> Result<u64, S::Error> {
+                        let mut guard = storage.lock().unwrap();
+                        let mut handle = match mode {
+                            Mode::Read => guard.read_handle(&objid)?,
+                            Mode::Write => guard.write_handle(&objid)?,
+                            Mode::ReadWrite => guard.rw_handle(&objid)?,
+                        };
+                        let pos = handle.seek(SeekFrom::End(n))?;
+                        Ok(pos)
+                    })
+                    .await
+                    .map_err(OffloadedError::Storage)?
+                }
+            };
+            Ok(self.pos)
+        }
+    }
+
+    impl<S> AsyncPersistentStorage for Offloaded<S>
+    where
+        S: PersistentStorage + Send + 'static,
+        S::Id: Clone + Send + 'static,
+        S::Flags: Clone + Send + 'static,
+        S::Info: Send + 'static,
+        S::Error: Send + 'static,
+        for<'a> S::Error: From<<S::Io<'a> as embedded_io::Io>::Error>,
+    {
+        type Id = S::Id;
+        type Flags = S::Flags;
+        type Info = S::Info;
+        type Error = S::Error;
+        type Io<'a>
+            = OffloadedIo<S>
+        where
+            Self: 'a;
+
+        async fn create(
+            &mut self,
+            objid: &Self::Id,
+            flags: &Self::Flags,
+        ) -> Result<(), Self::Error> {
+            let storage = self.0.clone();
+            let objid = objid.clone();
+            let flags = flags.clone();
+            spawn_blocking(move || storage.lock().unwrap().create(&objid, &flags)).await
+        }
+
+        async fn destroy(&mut self, objid: &Self::Id) -> Result<(), Self::Error> {
+            let storage = self.0.clone();
+            let objid = objid.clone();
+            spawn_blocking(move || storage.lock().unwrap().destroy(&objid)).await
+        }
+
+        async fn get_info(&mut self, objid: &Self::Id) -> Result<Self::Info, Self::Error> {
+            let storage = self.0.clone();
+            let objid = objid.clone();
+            spawn_blocking(move || storage.lock().unwrap().get_info(&objid)).await
+        }
+
+        async fn set_info(
+            &mut self,
+            objid: &Self::Id,
+            info: Self::Info,
+        ) -> Result<(), Self::Error> {
+            let storage = self.0.clone();
+            let objid = objid.clone();
+            spawn_blocking(move || storage.lock().unwrap().set_info(&objid, info)).await
+        }
+
+        async fn read_handle(&mut self, objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            Ok(OffloadedIo {
+                storage: self.0.clone(),
+                objid: objid.clone(),
+                mode: Mode::Read,
+                pos: 0,
+            })
+        }
+
+        async fn write_handle(&mut self, objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            Ok(OffloadedIo {
+                storage: self.0.clone(),
+                objid: objid.clone(),
+                mode: Mode::Write,
+                pos: 0,
+            })
+        }
+
+        async fn rw_handle(&mut self, objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            Ok(OffloadedIo {
+                storage: self.0.clone(),
+                objid: objid.clone(),
+                mode: Mode::ReadWrite,
+                pos: 0,
+            })
+        }
+
+        async fn truncate(&mut self, objid: &Self::Id, size: u64) -> Result<(), Self::Error> {
+            let storage = self.0.clone();
+            let objid = objid.clone();
+            spawn_blocking(move || storage.lock().unwrap().truncate(&objid, size)).await
+        }
+
+        async fn persist_state(&mut self) -> Result<(), Self::Error> {
+            let storage = self.0.clone();
+            spawn_blocking(move || storage.lock().unwrap().persist_state()).await
+        }
+
+        async fn load_state(&mut self) -> Result<(), Self::Error> {
+            let storage = self.0.clone();
+            spawn_blocking(move || storage.lock().unwrap().load_state()).await
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use core::convert::Infallible;
+
+        /// Drives `fut` to completion on the current thread, parking while
+        /// the spawned background thread does its work.
+        fn block_on<F: core::future::Future>(mut fut: F) -> F::Output {
+            use std::task::{Context, Wake};
+
+            struct ThreadWaker(std::thread::Thread);
+
+            impl Wake for ThreadWaker {
+                fn wake(self: Arc<Self>) {
+                    self.0.unpark();
+                }
+
+                fn wake_by_ref(self: &Arc<Self>) {
+                    self.0.unpark();
+                }
+            }
+
+            let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+            let mut cx = Context::from_waker(&waker);
+            // SAFETY: `fut` is not moved again before it is dropped at the end of
+            // this function.
+            let mut fut = unsafe { core::pin::Pin::new_unchecked(&mut fut) };
+            loop {
+                match fut.as_mut().poll(&mut cx) {
+                    Poll::Ready(value) => return value,
+                    Poll::Pending => std::thread::park(),
+                }
+            }
+        }
+
+        /// An in-memory [`PersistentStorage`] backing a single object, for
+        /// exercising [`Offloaded`]/[`OffloadedIo`] round trips.
+        struct StubStorage {
+            data: Vec<u8>,
+        }
+
+        struct StubIo<'a> {
+            data: &'a mut Vec<u8>,
+            pos: usize,
+        }
+
+        impl embedded_io::Io for StubIo<'_> {
+            type Error = Infallible;
+        }
+
+        /// `StubStorage`'s error type, kept distinct from `StubIo::Error` so
+        /// `OffloadedIo`'s conversion bound is actually exercised rather than
+        /// masked by both sides happening to be the same type.
+        #[derive(Debug)]
+        struct StorageError(Infallible);
+
+        impl From<Infallible> for StorageError {
+            fn from(err: Infallible) -> Self {
+                Self(err)
+            }
+        }
+
+        impl embedded_io::blocking::Read for StubIo<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+                let available = self.data.len().saturating_sub(self.pos);
+                let n = core::cmp::min(buf.len(), available);
+                buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+                self.pos += n;
+                Ok(n)
+            }
+        }
+
+        impl embedded_io::blocking::Write for StubIo<'_> {
+            fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+                let end = self.pos + buf.len();
+                if end > self.data.len() {
+                    self.data.resize(end, 0);
+                }
+                self.data[self.pos..end].copy_from_slice(buf);
+                self.pos = end;
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+
+        impl embedded_io::blocking::Seek for StubIo<'_> {
+            fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+                self.pos = match pos {
+                    SeekFrom::Start(n) => n as usize,
+                    SeekFrom::End(n) => (self.data.len() as i64 + n) as usize,
+                    SeekFrom::Current(n) => (self.pos as i64 + n) as usize,
+                };
+                Ok(self.pos as u64)
+            }
+        }
+
+        impl PersistentStorage for StubStorage {
+            type Id = u32;
+            type Flags = ();
+            type Info = ();
+            type Error = StorageError;
+            type Io<'a> = StubIo<'a>;
+
+            fn create(
+                &mut self,
+                _objid: &Self::Id,
+                _flags: &Self::Flags,
+            ) -> Result<(), Self::Error> {
+                Ok(())
+            }
+
+            fn destroy(&mut self, _objid: &Self::Id) -> Result<(), Self::Error> {
+                Ok(())
+            }
+
+            fn get_info(&mut self, _objid: &Self::Id) -> Result<Self::Info, Self::Error> {
+                Ok(())
+            }
+
+            fn set_info(
+                &mut self,
+                _objid: &Self::Id,
+                _info: Self::Info,
+            ) -> Result<(), Self::Error> {
+                Ok(())
+            }
+
+            fn read_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+                Ok(StubIo {
+                    data: &mut self.data,
+                    pos: 0,
+                })
+            }
+
+            fn write_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+                Ok(StubIo {
+                    data: &mut self.data,
+                    pos: 0,
+                })
+            }
+
+            fn rw_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+                Ok(StubIo {
+                    data: &mut self.data,
+                    pos: 0,
+                })
+            }
+
+            fn truncate(&mut self, _objid: &Self::Id, size: u64) -> Result<(), Self::Error> {
+                self.data.truncate(size as usize);
+                Ok(())
+            }
+
+            fn persist_state(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+
+            fn load_state(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+
+        #[test]
+        fn offloaded_write_then_read_round_trips() {
+            let mut storage = Offloaded::new(StubStorage { data: Vec::new() });
+            block_on(async {
+                let mut io = storage.write_handle(&1).await.unwrap();
+                Write::write(&mut io, b"hello world").await.unwrap();
+            });
+            block_on(async {
+                let mut io = storage.read_handle(&1).await.unwrap();
+                let mut buf = [0u8; 11];
+                let n = Read::read(&mut io, &mut buf).await.unwrap();
+                assert_eq!(n, 11);
+                assert_eq!(&buf, b"hello world");
+            });
+        }
+
+        #[test]
+        fn offloaded_write_flushes_the_handle_before_dropping_it() {
+            use std::sync::atomic::{AtomicU32, Ordering};
+
+            /// A storage stub whose `Io` counts `flush()` calls, standing in
+            /// for a backend that wraps its sink in something that batches
+            /// writes (e.g. a `BufWriter`) and only persists them on flush.
+            struct FlushCountingStorage {
+                data: Vec<u8>,
+                flushes: Arc<AtomicU32>,
+            }
+
+            struct FlushCountingIo<'a> {
+                data: &'a mut Vec<u8>,
+                flushes: Arc<AtomicU32>,
+                pos: usize,
+            }
+
+            impl embedded_io::Io for FlushCountingIo<'_> {
+                type Error = Infallible;
+            }
+
+            impl embedded_io::blocking::Read for FlushCountingIo<'_> {
+                fn read(&mut self, _buf: &mut [u8]) -> Result<usize, Self::Error> {
+                    Ok(0)
+                }
+            }
+
+            impl embedded_io::blocking::Write for FlushCountingIo<'_> {
+                fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+                    let end = self.pos + buf.len();
+                    if end > self.data.len() {
+                        self.data.resize(end, 0);
+                    }
+                    self.data[self.pos..end].copy_from_slice(buf);
+                    self.pos = end;
+                    Ok(buf.len())
+                }
+
+                fn flush(&mut self) -> Result<(), Self::Error> {
+                    self.flushes.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            }
+
+            impl embedded_io::blocking::Seek for FlushCountingIo<'_> {
+                fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+                    self.pos = match pos {
+                        SeekFrom::Start(n) => n as usize,
+                        SeekFrom::End(n) => (self.data.len() as i64 + n) as usize,
+                        SeekFrom::Current(n) => (self.pos as i64 + n) as usize,
+                    };
+                    Ok(self.pos as u64)
+                }
+            }
+
+            impl PersistentStorage for FlushCountingStorage {
+                type Id = u32;
+                type Flags = ();
+                type Info = ();
+                type Error = Infallible;
+                type Io<'a> = FlushCountingIo<'a>;
+
+                fn create(
+                    &mut self,
+                    _objid: &Self::Id,
+                    _flags: &Self::Flags,
+                ) -> Result<(), Self::Error> {
+                    Ok(())
+                }
+
+                fn destroy(&mut self, _objid: &Self::Id) -> Result<(), Self::Error> {
+                    Ok(())
+                }
+
+                fn get_info(&mut self, _objid: &Self::Id) -> Result<Self::Info, Self::Error> {
+                    Ok(())
+                }
+
+                fn set_info(
+                    &mut self,
+                    _objid: &Self::Id,
+                    _info: Self::Info,
+                ) -> Result<(), Self::Error> {
+                    Ok(())
+                }
+
+                fn read_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+                    Ok(FlushCountingIo {
+                        data: &mut self.data,
+                        flushes: self.flushes.clone(),
+                        pos: 0,
+                    })
+                }
+
+                fn write_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+                    Ok(FlushCountingIo {
+                        data: &mut self.data,
+                        flushes: self.flushes.clone(),
+                        pos: 0,
+                    })
+                }
+
+                fn rw_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+                    Ok(FlushCountingIo {
+                        data: &mut self.data,
+                        flushes: self.flushes.clone(),
+                        pos: 0,
+                    })
+                }
+
+                fn truncate(&mut self, _objid: &Self::Id, size: u64) -> Result<(), Self::Error> {
+                    self.data.truncate(size as usize);
+                    Ok(())
+                }
+
+                fn persist_state(&mut self) -> Result<(), Self::Error> {
+                    Ok(())
+                }
+
+                fn load_state(&mut self) -> Result<(), Self::Error> {
+                    Ok(())
+                }
+            }
+
+            let flushes = Arc::new(AtomicU32::new(0));
+            let mut storage = Offloaded::new(FlushCountingStorage {
+                data: Vec::new(),
+                flushes: flushes.clone(),
+            });
+            block_on(async {
+                let mut io = storage.write_handle(&1).await.unwrap();
+                Write::write(&mut io, b"hello").await.unwrap();
+            });
+            assert_eq!(flushes.load(Ordering::SeqCst), 1);
+        }
+
+        #[test]
+        fn offloaded_rw_handle_reads_back_what_it_writes() {
+            let mut storage = Offloaded::new(StubStorage { data: Vec::new() });
+            block_on(async {
+                let mut io = storage.rw_handle(&1).await.unwrap();
+                Write::write(&mut io, b"abc").await.unwrap();
+                Seek::seek(&mut io, SeekFrom::Start(0)).await.unwrap();
+                let mut buf = [0u8; 3];
+                Read::read(&mut io, &mut buf).await.unwrap();
+                assert_eq!(&buf, b"abc");
+            });
+        }
+
+        #[test]
+        fn offloaded_read_only_handle_rejects_writes() {
+            let mut storage = Offloaded::new(StubStorage { data: Vec::new() });
+            block_on(async {
+                let mut io = storage.read_handle(&1).await.unwrap();
+                assert!(matches!(
+                    Write::write(&mut io, b"x").await,
+                    Err(OffloadedError::ModeViolation)
+                ));
+            });
+        }
+
+        #[test]
+        fn offloaded_write_only_handle_rejects_reads() {
+            let mut storage = Offloaded::new(StubStorage { data: Vec::new() });
+            block_on(async {
+                let mut io = storage.write_handle(&1).await.unwrap();
+                let mut buf = [0u8; 1];
+                assert!(matches!(
+                    Read::read(&mut io, &mut buf).await,
+                    Err(OffloadedError::ModeViolation)
+                ));
+            });
+        }
+
+        #[test]
+        fn offloaded_seek_current_tracks_position_without_touching_storage() {
+            let mut storage = Offloaded::new(StubStorage {
+                data: b"hello world".to_vec(),
+            });
+            block_on(async {
+                let mut io = storage.read_handle(&1).await.unwrap();
+                Seek::seek(&mut io, SeekFrom::Start(2)).await.unwrap();
+                let pos = Seek::seek(&mut io, SeekFrom::Current(3)).await.unwrap();
+                assert_eq!(pos, 5);
+                let mut buf = [0u8; 6];
+                let n = Read::read(&mut io, &mut buf).await.unwrap();
+                assert_eq!(&buf[..n], b" world");
+            });
+        }
+
+        #[test]
+        fn offloaded_seek_current_saturates_instead_of_overflowing() {
+            let mut storage = Offloaded::new(StubStorage { data: Vec::new() });
+            block_on(async {
+                let mut io = storage.read_handle(&1).await.unwrap();
+                Seek::seek(&mut io, SeekFrom::Start(u64::MAX))
+                    .await
+                    .unwrap();
+                let pos = Seek::seek(&mut io, SeekFrom::Current(i64::MAX))
+                    .await
+                    .unwrap();
+                assert_eq!(pos, u64::MAX);
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    /// Drives a `Future` that is always immediately `Ready`, as every
+    /// `Blocking` operation is.
+    fn block_on<F: core::future::Future>(fut: F) -> F::Output {
+        use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+        fn noop(_: *const ()) {}
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(core::ptr::null(), &VTABLE)
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+        let mut cx = Context::from_waker(&waker);
+        let mut fut = core::pin::pin!(fut);
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(value) => value,
+            Poll::Pending => panic!("Blocking should never return Pending"),
+        }
+    }
+
+    /// An in-memory [`PersistentStorage`] backing a single object.
+    struct StubStorage {
+        data: Vec<u8>,
+    }
+
+    struct StubIo<'a>(&'a mut Vec<u8>, usize);
+
+    impl embedded_io::Io for StubIo<'_> {
+        type Error = Infallible;
+    }
+
+    impl embedded_io::blocking::Read for StubIo<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let available = self.0.len().saturating_sub(self.1);
+            let n = core::cmp::min(buf.len(), available);
+            buf[..n].copy_from_slice(&self.0[self.1..self.1 + n]);
+            self.1 += n;
+            Ok(n)
+        }
+    }
+
+    impl embedded_io::blocking::Write for StubIo<'_> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            let end = self.1 + buf.len();
+            if end > self.0.len() {
+                self.0.resize(end, 0);
+            }
+            self.0[self.1..end].copy_from_slice(buf);
+            self.1 = end;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl embedded_io::blocking::Seek for StubIo<'_> {
+        fn seek(&mut self, pos: embedded_io::SeekFrom) -> Result<u64, Self::Error> {
+            self.1 = match pos {
+                embedded_io::SeekFrom::Start(n) => n as usize,
+                embedded_io::SeekFrom::End(n) => (self.0.len() as i64 + n) as usize,
+                embedded_io::SeekFrom::Current(n) => (self.1 as i64 + n) as usize,
+            };
+            Ok(self.1 as u64)
+        }
+    }
+
+    impl PersistentStorage for StubStorage {
+        type Id = u32;
+        type Flags = ();
+        type Info = ();
+        type Error = Infallible;
+        type Io<'a> = StubIo<'a>;
+
+        fn create(&mut self, _objid: &Self::Id, _flags: &Self::Flags) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn destroy(&mut self, _objid: &Self::Id) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn get_info(&mut self, _objid: &Self::Id) -> Result<Self::Info, Self::Error> {
+            Ok(())
+        }
+
+        fn set_info(&mut self, _objid: &Self::Id, _info: Self::Info) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn read_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            Ok(StubIo(&mut self.data, 0))
+        }
+
+        fn write_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            Ok(StubIo(&mut self.data, 0))
+        }
+
+        fn rw_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            Ok(StubIo(&mut self.data, 0))
+        }
+
+        fn truncate(&mut self, _objid: &Self::Id, size: u64) -> Result<(), Self::Error> {
+            self.data.truncate(size as usize);
+            Ok(())
+        }
+
+        fn persist_state(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn load_state(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn blocking_delegates_writes_and_reads_to_the_inner_storage() {
+        let mut storage = Blocking::new(StubStorage { data: Vec::new() });
+        block_on(async {
+            let mut io = storage.write_handle(&1).await.unwrap();
+            Write::write(&mut io, b"hello").await.unwrap();
+        });
+        assert_eq!(storage.get_ref().data, b"hello");
+        block_on(async {
+            let mut io = storage.read_handle(&1).await.unwrap();
+            let mut buf = [0u8; 5];
+            Read::read(&mut io, &mut buf).await.unwrap();
+            assert_eq!(&buf, b"hello");
+        });
+    }
+
+    #[test]
+    fn blocking_seek_delegates_to_the_inner_storage() {
+        let mut storage = Blocking::new(StubStorage {
+            data: b"hello world".to_vec(),
+        });
+        block_on(async {
+            let mut io = storage.rw_handle(&1).await.unwrap();
+            let pos = Seek::seek(&mut io, embedded_io::SeekFrom::End(-5))
+                .await
+                .unwrap();
+            assert_eq!(pos, 6);
+            let mut buf = [0u8; 5];
+            Read::read(&mut io, &mut buf).await.unwrap();
+            assert_eq!(&buf, b"world");
+        });
+    }
+}
@@ -0,0 +1,355 @@
+//! Buffered wrappers over `embedded_io` streams.
+
+use embedded_io::{
+    blocking::{Read, Write},
+    Error as EioError, ErrorKind, Io,
+};
+
+/// Extends `Read` with the ability to peek at buffered data without consuming it.
+pub trait BufRead: Read {
+    /// Returns the contents of the internal buffer, filling it from the
+    /// underlying stream if it is empty.
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error>;
+
+    /// Marks `amt` bytes of the buffer returned by `fill_buf` as consumed.
+    fn consume(&mut self, amt: usize);
+}
+
+/// Wraps a reader in a fixed-size buffer to reduce the number of calls into
+/// the underlying stream.
+pub struct BufReader<R, const N: usize = 512> {
+    inner: R,
+    buf: [u8; N],
+    cap: usize,
+    pos: usize,
+    len: usize,
+}
+
+impl<R, const N: usize> BufReader<R, N> {
+    /// Wraps `inner`, using the full `N`-byte buffer.
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(N, inner)
+    }
+
+    /// Wraps `inner`, using only the first `capacity` bytes of the buffer.
+    ///
+    /// Panics if `capacity` exceeds `N`.
+    pub fn with_capacity(capacity: usize, inner: R) -> Self {
+        assert!(capacity <= N, "capacity exceeds buffer size");
+        Self {
+            inner,
+            buf: [0; N],
+            cap: capacity,
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Reading directly through this reference bypasses the buffer.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `BufReader`, returning the underlying reader.
+    ///
+    /// Any buffered but unconsumed data is discarded.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, const N: usize> Io for BufReader<R, N> {
+    type Error = R::Error;
+}
+
+impl<R: Read, const N: usize> BufRead for BufReader<R, N> {
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        if self.pos >= self.len {
+            self.len = self.inner.read(&mut self.buf[..self.cap])?;
+            self.pos = 0;
+        }
+        Ok(&self.buf[self.pos..self.len])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = core::cmp::min(self.pos + amt, self.len);
+    }
+}
+
+impl<R: Read, const N: usize> Read for BufReader<R, N> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let available = self.fill_buf()?;
+        let n = core::cmp::min(buf.len(), available.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+/// The error returned by [`BufWriter::into_inner`] when the final flush fails.
+///
+/// Carries both the underlying writer and the flush error so the caller can
+/// recover the writer instead of losing the buffered bytes silently.
+#[derive(Debug)]
+pub struct IntoInnerError<W, E>(W, E);
+
+impl<W, E> IntoInnerError<W, E> {
+    /// Returns the error that occurred while flushing.
+    pub fn error(&self) -> &E {
+        &self.1
+    }
+
+    /// Returns the underlying writer, discarding the flush error.
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+
+    /// Splits this error into its writer and flush error components.
+    pub fn into_parts(self) -> (W, E) {
+        (self.0, self.1)
+    }
+}
+
+/// Wraps a writer in a fixed-size buffer, coalescing small writes into
+/// larger ones against the underlying stream.
+pub struct BufWriter<W, const N: usize = 512> {
+    inner: Option<W>,
+    buf: [u8; N],
+    cap: usize,
+    len: usize,
+}
+
+impl<W: Write, const N: usize> BufWriter<W, N> {
+    /// Wraps `inner`, using the full `N`-byte buffer.
+    pub fn new(inner: W) -> Self {
+        Self::with_capacity(N, inner)
+    }
+
+    /// Wraps `inner`, using only the first `capacity` bytes of the buffer.
+    ///
+    /// Panics if `capacity` is zero or exceeds `N`. A zero-capacity
+    /// `BufWriter` could never make progress on a non-empty `write()`,
+    /// which would violate `embedded_io::blocking::Write`'s contract for
+    /// callers going through its `write_all`/`write_fmt` default methods.
+    pub fn with_capacity(capacity: usize, inner: W) -> Self {
+        assert!(capacity > 0, "capacity must be nonzero");
+        assert!(capacity <= N, "capacity exceeds buffer size");
+        Self {
+            inner: Some(inner),
+            buf: [0; N],
+            cap: capacity,
+            len: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("writer taken")
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Writing directly through this reference bypasses the buffer.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().expect("writer taken")
+    }
+
+    fn flush_buf(&mut self) -> Result<(), FlushError<W::Error>> {
+        let writer = self.inner.as_mut().expect("writer taken");
+        let mut written = 0;
+        while written < self.len {
+            match writer
+                .write(&self.buf[written..self.len])
+                .map_err(FlushError::Storage)?
+            {
+                0 => return Err(FlushError::WriteZero),
+                n => written += n,
+            }
+        }
+        self.len = 0;
+        Ok(())
+    }
+
+    /// Unwraps this `BufWriter`, flushing any buffered data first.
+    ///
+    /// If the final flush fails, the writer and the error are returned
+    /// together in an [`IntoInnerError`] rather than dropping either.
+    pub fn into_inner(mut self) -> Result<W, IntoInnerError<W, FlushError<W::Error>>> {
+        match self.flush_buf() {
+            Ok(()) => Ok(self.inner.take().expect("writer taken")),
+            Err(err) => Err(IntoInnerError(
+                self.inner.take().expect("writer taken"),
+                err,
+            )),
+        }
+    }
+}
+
+/// The error returned when flushing a [`BufWriter`] fails.
+#[derive(Debug)]
+pub enum FlushError<E> {
+    /// The underlying writer returned an error.
+    Storage(E),
+    /// The underlying writer returned `Ok(0)` without making progress.
+    WriteZero,
+}
+
+impl<E: EioError> EioError for FlushError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            FlushError::Storage(err) => err.kind(),
+            // `embedded_io::ErrorKind` has no variant for this; `Storage`
+            // errors still forward their own `kind()` above.
+            FlushError::WriteZero => ErrorKind::Other,
+        }
+    }
+}
+
+impl<W: Write, const N: usize> Io for BufWriter<W, N> {
+    type Error = FlushError<W::Error>;
+}
+
+impl<W: Write, const N: usize> Write for BufWriter<W, N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if self.len >= self.cap {
+            self.flush_buf()?;
+        }
+        let n = core::cmp::min(buf.len(), self.cap - self.len);
+        self.buf[self.len..self.len + n].copy_from_slice(&buf[..n]);
+        self.len += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.flush_buf()?;
+        self.inner
+            .as_mut()
+            .expect("writer taken")
+            .flush()
+            .map_err(FlushError::Storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    struct SliceReader<'a> {
+        data: &'a [u8],
+        pos: usize,
+    }
+
+    impl Io for SliceReader<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for SliceReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let n = core::cmp::min(buf.len(), self.data.len() - self.pos);
+            buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+            self.pos += n;
+            Ok(n)
+        }
+    }
+
+    #[derive(Debug)]
+    struct VecWriter(Vec<u8>);
+
+    impl Io for VecWriter {
+        type Error = Infallible;
+    }
+
+    impl Write for VecWriter {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    /// A writer that always reports zero bytes written without erroring.
+    #[derive(Debug)]
+    struct StalledWriter;
+
+    impl Io for StalledWriter {
+        type Error = Infallible;
+    }
+
+    impl Write for StalledWriter {
+        fn write(&mut self, _buf: &[u8]) -> Result<usize, Self::Error> {
+            Ok(0)
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn buf_reader_reads_through_small_buffer() {
+        let mut reader: BufReader<_, 4> = BufReader::new(SliceReader {
+            data: b"hello world",
+            pos: 0,
+        });
+        let mut out = [0u8; 11];
+        let mut total = 0;
+        while total < out.len() {
+            let n = reader.read(&mut out[total..]).unwrap();
+            assert!(n > 0, "reader should not stall before EOF");
+            total += n;
+        }
+        assert_eq!(&out, b"hello world");
+    }
+
+    #[test]
+    fn buf_writer_coalesces_and_flushes() {
+        let writer: BufWriter<_, 4> = BufWriter::new(VecWriter(Vec::new()));
+        let mut writer = writer;
+        writer.write(b"ab").unwrap();
+        writer.write(b"cd").unwrap();
+        // Still buffered: nothing reached the inner writer yet.
+        assert!(writer.get_ref().0.is_empty());
+        writer.write(b"e").unwrap();
+        assert_eq!(writer.get_ref().0, b"abcd");
+        let inner = writer.into_inner().unwrap();
+        assert_eq!(inner.0, b"abcde");
+    }
+
+    #[test]
+    fn zero_capacity_buf_reader_reports_eof_without_panicking() {
+        let mut reader: BufReader<_, 8> = BufReader::with_capacity(
+            0,
+            SliceReader {
+                data: b"hello",
+                pos: 0,
+            },
+        );
+        let mut out = [0u8; 8];
+        assert_eq!(reader.read(&mut out).unwrap(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be nonzero")]
+    fn zero_capacity_buf_writer_is_rejected() {
+        let _: BufWriter<_, 8> = BufWriter::with_capacity(0, VecWriter(Vec::new()));
+    }
+
+    #[test]
+    fn buf_writer_into_inner_reports_flush_failure() {
+        let mut writer: BufWriter<_, 4> = BufWriter::new(StalledWriter);
+        writer.write(b"ab").unwrap();
+        let err = writer.into_inner().unwrap_err();
+        assert!(matches!(err.error(), FlushError::WriteZero));
+    }
+}
@@ -0,0 +1,288 @@
+//! Error context for [`PersistentStorage`] operations.
+
+use crate::PersistentStorage;
+use core::fmt::{self, Debug, Display};
+
+/// The `PersistentStorage` operation an error occurred during.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Create,
+    Destroy,
+    GetInfo,
+    SetInfo,
+    ReadHandle,
+    WriteHandle,
+    RwHandle,
+    Truncate,
+    PersistState,
+    LoadState,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Operation::Create => "create",
+            Operation::Destroy => "destroy",
+            Operation::GetInfo => "get_info",
+            Operation::SetInfo => "set_info",
+            Operation::ReadHandle => "read_handle",
+            Operation::WriteHandle => "write_handle",
+            Operation::RwHandle => "rw_handle",
+            Operation::Truncate => "truncate",
+            Operation::PersistState => "persist_state",
+            Operation::LoadState => "load_state",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Wraps a `PersistentStorage::Error` with the operation and object it
+/// occurred on.
+#[derive(Debug)]
+pub struct ContextError<E, Id> {
+    op: Operation,
+    id: Option<Id>,
+    source: E,
+}
+
+impl<E, Id> ContextError<E, Id> {
+    /// Wraps `source` with the given operation and, if applicable, object id.
+    pub fn new(op: Operation, id: Option<Id>, source: E) -> Self {
+        Self { op, id, source }
+    }
+
+    /// The operation that failed.
+    pub fn operation(&self) -> Operation {
+        self.op
+    }
+
+    /// The object the operation was performed on, if any.
+    pub fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    /// The underlying error.
+    pub fn source(&self) -> &E {
+        &self.source
+    }
+
+    /// Unwraps this error, discarding the operation and object id.
+    pub fn into_source(self) -> E {
+        self.source
+    }
+}
+
+impl<E: Debug, Id: Debug> Display for ContextError<E, Id> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.id {
+            Some(id) => write!(
+                f,
+                "{} failed for object {:?}: {:?}",
+                self.op, id, self.source
+            ),
+            None => write!(f, "{} failed: {:?}", self.op, self.source),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Debug, Id: Debug> std::error::Error for ContextError<E, Id> {}
+
+/// Extends [`PersistentStorage`] with versions of its methods that wrap
+/// errors in a [`ContextError`] naming the operation and object involved.
+///
+/// Blanket-implemented for every `PersistentStorage`, so backends opt in
+/// without reimplementing error handling.
+pub trait PersistentStorageExt: PersistentStorage {
+    fn create_ctx(&mut self, objid: &Self::Id, flags: &Self::Flags) -> CtxResult<(), Self>
+    where
+        Self::Id: Clone,
+    {
+        self.create(objid, flags)
+            .map_err(|err| ContextError::new(Operation::Create, Some(objid.clone()), err))
+    }
+
+    fn destroy_ctx(&mut self, objid: &Self::Id) -> CtxResult<(), Self>
+    where
+        Self::Id: Clone,
+    {
+        self.destroy(objid)
+            .map_err(|err| ContextError::new(Operation::Destroy, Some(objid.clone()), err))
+    }
+
+    fn get_info_ctx(&mut self, objid: &Self::Id) -> CtxResult<Self::Info, Self>
+    where
+        Self::Id: Clone,
+    {
+        self.get_info(objid)
+            .map_err(|err| ContextError::new(Operation::GetInfo, Some(objid.clone()), err))
+    }
+
+    fn set_info_ctx(&mut self, objid: &Self::Id, info: Self::Info) -> CtxResult<(), Self>
+    where
+        Self::Id: Clone,
+    {
+        self.set_info(objid, info)
+            .map_err(|err| ContextError::new(Operation::SetInfo, Some(objid.clone()), err))
+    }
+
+    fn read_handle_ctx(&mut self, objid: &Self::Id) -> CtxResult<Self::Io<'_>, Self>
+    where
+        Self::Id: Clone,
+    {
+        self.read_handle(objid)
+            .map_err(|err| ContextError::new(Operation::ReadHandle, Some(objid.clone()), err))
+    }
+
+    fn write_handle_ctx(&mut self, objid: &Self::Id) -> CtxResult<Self::Io<'_>, Self>
+    where
+        Self::Id: Clone,
+    {
+        self.write_handle(objid)
+            .map_err(|err| ContextError::new(Operation::WriteHandle, Some(objid.clone()), err))
+    }
+
+    fn rw_handle_ctx(&mut self, objid: &Self::Id) -> CtxResult<Self::Io<'_>, Self>
+    where
+        Self::Id: Clone,
+    {
+        self.rw_handle(objid)
+            .map_err(|err| ContextError::new(Operation::RwHandle, Some(objid.clone()), err))
+    }
+
+    fn truncate_ctx(&mut self, objid: &Self::Id, size: u64) -> CtxResult<(), Self>
+    where
+        Self::Id: Clone,
+    {
+        self.truncate(objid, size)
+            .map_err(|err| ContextError::new(Operation::Truncate, Some(objid.clone()), err))
+    }
+
+    fn persist_state_ctx(&mut self) -> CtxResult<(), Self> {
+        self.persist_state()
+            .map_err(|err| ContextError::new(Operation::PersistState, None, err))
+    }
+
+    fn load_state_ctx(&mut self) -> CtxResult<(), Self> {
+        self.load_state()
+            .map_err(|err| ContextError::new(Operation::LoadState, None, err))
+    }
+}
+
+/// Shorthand for the `Result` returned by [`PersistentStorageExt`] methods.
+type CtxResult<T, S> =
+    Result<T, ContextError<<S as PersistentStorage>::Error, <S as PersistentStorage>::Id>>;
+
+impl<T: PersistentStorage> PersistentStorageExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    struct StubIo;
+
+    impl embedded_io::Io for StubIo {
+        type Error = Infallible;
+    }
+
+    impl embedded_io::blocking::Read for StubIo {
+        fn read(&mut self, _buf: &mut [u8]) -> Result<usize, Self::Error> {
+            Ok(0)
+        }
+    }
+
+    impl embedded_io::blocking::Write for StubIo {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl embedded_io::blocking::Seek for StubIo {
+        fn seek(&mut self, _pos: embedded_io::SeekFrom) -> Result<u64, Self::Error> {
+            Ok(0)
+        }
+    }
+
+    /// A storage stub whose `create`/`persist_state` always fail, so the
+    /// `*_ctx` wrappers have something to attach context to.
+    struct FailingStorage;
+
+    impl PersistentStorage for FailingStorage {
+        type Id = u32;
+        type Flags = ();
+        type Info = ();
+        type Error = &'static str;
+        type Io<'a> = StubIo;
+
+        fn create(&mut self, _objid: &Self::Id, _flags: &Self::Flags) -> Result<(), Self::Error> {
+            Err("disk full")
+        }
+
+        fn destroy(&mut self, _objid: &Self::Id) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn get_info(&mut self, _objid: &Self::Id) -> Result<Self::Info, Self::Error> {
+            Ok(())
+        }
+
+        fn set_info(&mut self, _objid: &Self::Id, _info: Self::Info) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn read_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            Ok(StubIo)
+        }
+
+        fn write_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            Ok(StubIo)
+        }
+
+        fn rw_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            Ok(StubIo)
+        }
+
+        fn truncate(&mut self, _objid: &Self::Id, _size: u64) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn persist_state(&mut self) -> Result<(), Self::Error> {
+            Err("no fixed place")
+        }
+
+        fn load_state(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn create_ctx_wraps_the_operation_and_object_id() {
+        let mut storage = FailingStorage;
+        let err = storage.create_ctx(&7, &()).unwrap_err();
+        assert_eq!(err.operation(), Operation::Create);
+        assert_eq!(err.id(), Some(&7));
+        assert_eq!(*err.source(), "disk full");
+    }
+
+    #[test]
+    fn persist_state_ctx_wraps_without_an_object_id() {
+        let mut storage = FailingStorage;
+        let err = storage.persist_state_ctx().unwrap_err();
+        assert_eq!(err.operation(), Operation::PersistState);
+        assert_eq!(err.id(), None);
+    }
+
+    #[test]
+    fn display_reads_like_a_path_plus_kind_error() {
+        let err = ContextError::new(Operation::Truncate, Some(7), "disk full");
+        assert_eq!(
+            format!("{err}"),
+            "truncate failed for object 7: \"disk full\""
+        );
+    }
+}
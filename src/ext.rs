@@ -0,0 +1,239 @@
+//! Convenience read/write helpers and a `copy` routine over `embedded_io`
+//! streams, mirroring the stabilized `std::io` helper set.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::fmt::{self, Debug, Display};
+use embedded_io::blocking::{Read, Write};
+
+/// Extends [`Read`] with a helper that drains the stream into a growable
+/// buffer.
+#[cfg(feature = "alloc")]
+pub trait ReadExt: Read {
+    /// Reads until EOF, appending to `buf` and returning the number of
+    /// bytes appended.
+    fn read_to_end(&mut self, buf: &mut alloc::vec::Vec<u8>) -> Result<usize, Self::Error> {
+        let start_len = buf.len();
+        let mut chunk = [0u8; 64];
+        loop {
+            match self.read(&mut chunk)? {
+                0 => break,
+                n => buf.extend_from_slice(&chunk[..n]),
+            }
+        }
+        Ok(buf.len() - start_len)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Read> ReadExt for T {}
+
+/// The error returned by [`WriteExt::try_write_all`] when the stream stops
+/// making progress.
+#[derive(Debug)]
+pub enum WriteAllError<E> {
+    /// `write` returned `Ok(0)` before all of the buffer was written.
+    WriteZero,
+    /// The underlying stream returned an error.
+    Other(E),
+}
+
+impl<E: Debug> Display for WriteAllError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteAllError::WriteZero => write!(f, "failed to write whole buffer"),
+            WriteAllError::Other(err) => write!(f, "{err:?}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Debug> std::error::Error for WriteAllError<E> {}
+
+/// Extends [`Write`] with a helper that retries short writes until the
+/// whole buffer is written.
+///
+/// Named `try_write_all` rather than `write_all` because `embedded_io`'s
+/// own `Write` trait already ships a default `write_all` method; giving
+/// this one the same name would make every call through a type that has
+/// both in scope ambiguous.
+pub trait WriteExt: Write {
+    /// Writes the entirety of `buf`, retrying on short writes.
+    fn try_write_all(&mut self, mut buf: &[u8]) -> Result<(), WriteAllError<Self::Error>> {
+        while !buf.is_empty() {
+            match self.write(buf).map_err(WriteAllError::Other)? {
+                0 => return Err(WriteAllError::WriteZero),
+                n => buf = &buf[n..],
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Write> WriteExt for T {}
+
+/// The error returned by [`copy`].
+#[derive(Debug)]
+pub enum CopyError<R, W> {
+    /// Reading from the source failed.
+    Read(R),
+    /// Writing to the destination failed.
+    Write(W),
+    /// The destination stopped making progress.
+    WriteZero,
+}
+
+impl<R: Debug, W: Debug> Display for CopyError<R, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyError::Read(err) => write!(f, "read failed: {err:?}"),
+            CopyError::Write(err) => write!(f, "write failed: {err:?}"),
+            CopyError::WriteZero => write!(f, "failed to write whole buffer"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Debug, W: Debug> std::error::Error for CopyError<R, W> {}
+
+/// Streams `src` into `dst` through `buf` until `src` reaches EOF (a
+/// zero-length read), returning the total number of bytes copied.
+///
+/// Short writes are retried against the remainder of each chunk before more
+/// is read from `src`.
+pub fn copy<R, W>(
+    src: &mut R,
+    dst: &mut W,
+    buf: &mut [u8],
+) -> Result<u64, CopyError<R::Error, W::Error>>
+where
+    R: Read,
+    W: Write,
+{
+    let mut total = 0u64;
+    loop {
+        let n = src.read(buf).map_err(CopyError::Read)?;
+        if n == 0 {
+            break;
+        }
+        let mut written = 0;
+        while written < n {
+            match dst.write(&buf[written..n]).map_err(CopyError::Write)? {
+                0 => return Err(CopyError::WriteZero),
+                w => written += w,
+            }
+        }
+        total += n as u64;
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    struct SliceReader<'a> {
+        data: &'a [u8],
+        pos: usize,
+    }
+
+    impl embedded_io::Io for SliceReader<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for SliceReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let n = core::cmp::min(buf.len(), self.data.len() - self.pos);
+            buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+            self.pos += n;
+            Ok(n)
+        }
+    }
+
+    struct VecWriter(Vec<u8>);
+
+    impl embedded_io::Io for VecWriter {
+        type Error = Infallible;
+    }
+
+    impl Write for VecWriter {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    struct StalledWriter;
+
+    impl embedded_io::Io for StalledWriter {
+        type Error = Infallible;
+    }
+
+    impl Write for StalledWriter {
+        fn write(&mut self, _buf: &[u8]) -> Result<usize, Self::Error> {
+            Ok(0)
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn read_to_end_drains_the_stream() {
+        let mut reader = SliceReader {
+            data: b"hello world",
+            pos: 0,
+        };
+        let mut buf = alloc::vec::Vec::new();
+        let n = reader.read_to_end(&mut buf).unwrap();
+        assert_eq!(n, 11);
+        assert_eq!(buf, b"hello world");
+    }
+
+    #[test]
+    fn try_write_all_writes_the_whole_buffer() {
+        let mut writer = VecWriter(Vec::new());
+        writer.try_write_all(b"hello world").unwrap();
+        assert_eq!(writer.0, b"hello world");
+    }
+
+    #[test]
+    fn try_write_all_reports_write_zero() {
+        let mut writer = StalledWriter;
+        let err = writer.try_write_all(b"x").unwrap_err();
+        assert!(matches!(err, WriteAllError::WriteZero));
+    }
+
+    #[test]
+    fn copy_streams_all_bytes_through_a_small_buffer() {
+        let mut src = SliceReader {
+            data: b"hello world",
+            pos: 0,
+        };
+        let mut dst = VecWriter(Vec::new());
+        let mut scratch = [0u8; 4];
+        let n = copy(&mut src, &mut dst, &mut scratch).unwrap();
+        assert_eq!(n, 11);
+        assert_eq!(dst.0, b"hello world");
+    }
+
+    #[test]
+    fn copy_reports_write_zero() {
+        let mut src = SliceReader {
+            data: b"hello",
+            pos: 0,
+        };
+        let mut dst = StalledWriter;
+        let mut scratch = [0u8; 4];
+        let err = copy(&mut src, &mut dst, &mut scratch).unwrap_err();
+        assert!(matches!(err, CopyError::WriteZero));
+    }
+}
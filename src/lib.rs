@@ -1,6 +1,19 @@
 use core::fmt::Debug;
 use embedded_io::blocking::{Read, Seek, Write};
 
+#[cfg(feature = "std")]
+pub mod stdio;
+
+#[cfg(feature = "async")]
+pub mod asynch;
+
+pub mod buffered;
+pub mod error;
+pub mod ext;
+
+#[cfg(feature = "alloc")]
+pub mod resource;
+
 /// A trait for persisting and loading objects using `Io`s.
 pub trait Persist<Io>: Sized
 where
@@ -0,0 +1,310 @@
+//! A file-descriptor-style table of handles over a [`PersistentStorage`].
+
+extern crate alloc;
+
+use crate::PersistentStorage;
+use alloc::collections::BTreeMap;
+use core::fmt::{self, Debug, Display};
+
+/// An opaque, copyable descriptor for an object opened in a [`ResourceTable`].
+pub type Handle = u32;
+
+/// The access mode a [`Handle`] was opened with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl Mode {
+    fn readable(self) -> bool {
+        matches!(self, Mode::Read | Mode::ReadWrite)
+    }
+
+    fn writable(self) -> bool {
+        matches!(self, Mode::Write | Mode::ReadWrite)
+    }
+}
+
+/// Errors returned by [`ResourceTable`].
+#[derive(Debug)]
+pub enum Error<E> {
+    /// No object is open under the given handle.
+    BadHandle(Handle),
+    /// The handle's mode does not permit the attempted access.
+    ModeViolation(Handle, Mode),
+    /// The underlying storage returned an error.
+    Storage(E),
+    /// Every handle id has already been issued.
+    HandlesExhausted,
+}
+
+impl<E: Debug> Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BadHandle(handle) => write!(f, "no object open under handle {handle}"),
+            Error::ModeViolation(handle, mode) => {
+                write!(
+                    f,
+                    "handle {handle} opened as {mode:?} does not permit this access"
+                )
+            }
+            Error::Storage(err) => write!(f, "storage error: {err:?}"),
+            Error::HandlesExhausted => write!(f, "every handle id has already been issued"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Debug> std::error::Error for Error<E> {}
+
+/// Metadata tracked for a handle: the object it refers to and the mode it
+/// was opened with.
+struct Entry<Id> {
+    objid: Id,
+    mode: Mode,
+}
+
+/// A table that hands out opaque, copyable [`Handle`]s for objects opened in
+/// a [`PersistentStorage`], decoupling handle lifetime from the borrow
+/// checker.
+///
+/// Handles are looked up lazily: the table only tracks the object id and
+/// mode each handle was opened with, and asks the underlying storage for an
+/// `Io` on every [`get`](Self::get)/[`get_mut`](Self::get_mut) call.
+pub struct ResourceTable<S: PersistentStorage> {
+    storage: S,
+    entries: BTreeMap<Handle, Entry<S::Id>>,
+    next: u64,
+}
+
+impl<S: PersistentStorage> ResourceTable<S> {
+    /// Creates an empty table over `storage`.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            entries: BTreeMap::new(),
+            next: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying storage.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Returns a mutable reference to the underlying storage.
+    pub fn storage_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    /// Opens `objid` with the given `mode` and returns a new, monotonically
+    /// increasing handle for it.
+    ///
+    /// Does not itself validate that the object exists; that happens on the
+    /// first [`get`](Self::get)/[`get_mut`](Self::get_mut) call. Fails once
+    /// every `Handle` value has been issued, rather than wrapping around and
+    /// risking a collision with a still-open handle.
+    pub fn open(&mut self, objid: S::Id, mode: Mode) -> Result<Handle, Error<S::Error>> {
+        let handle = Handle::try_from(self.next).map_err(|_| Error::HandlesExhausted)?;
+        self.next += 1;
+        self.entries.insert(handle, Entry { objid, mode });
+        Ok(handle)
+    }
+
+    /// Closes `handle`, forgetting the object it referred to.
+    pub fn close(&mut self, handle: Handle) -> Result<(), Error<S::Error>> {
+        self.entries
+            .remove(&handle)
+            .map(|_| ())
+            .ok_or(Error::BadHandle(handle))
+    }
+
+    /// Returns a read `Io` handle for `handle`, failing if it was not opened
+    /// for reading.
+    pub fn get(&mut self, handle: Handle) -> Result<S::Io<'_>, Error<S::Error>> {
+        let entry = self.entries.get(&handle).ok_or(Error::BadHandle(handle))?;
+        if !entry.mode.readable() {
+            return Err(Error::ModeViolation(handle, entry.mode));
+        }
+        match entry.mode {
+            Mode::ReadWrite => self.storage.rw_handle(&entry.objid),
+            _ => self.storage.read_handle(&entry.objid),
+        }
+        .map_err(Error::Storage)
+    }
+
+    /// Returns a write `Io` handle for `handle`, failing if it was not
+    /// opened for writing.
+    pub fn get_mut(&mut self, handle: Handle) -> Result<S::Io<'_>, Error<S::Error>> {
+        let entry = self.entries.get(&handle).ok_or(Error::BadHandle(handle))?;
+        if !entry.mode.writable() {
+            return Err(Error::ModeViolation(handle, entry.mode));
+        }
+        match entry.mode {
+            Mode::ReadWrite => self.storage.rw_handle(&entry.objid),
+            _ => self.storage.write_handle(&entry.objid),
+        }
+        .map_err(Error::Storage)
+    }
+
+    /// Iterates over the handles currently open, along with the object id
+    /// and mode each was opened with.
+    pub fn handles(&self) -> impl Iterator<Item = (Handle, &S::Id, Mode)> {
+        self.entries
+            .iter()
+            .map(|(&handle, entry)| (handle, &entry.objid, entry.mode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    /// A storage stub backing a single object, recording which `*_handle`
+    /// method was last called so dispatch can be asserted on.
+    struct StubStorage {
+        last_handle_call: Option<&'static str>,
+    }
+
+    struct StubIo;
+
+    impl embedded_io::Io for StubIo {
+        type Error = Infallible;
+    }
+
+    impl embedded_io::blocking::Read for StubIo {
+        fn read(&mut self, _buf: &mut [u8]) -> Result<usize, Self::Error> {
+            Ok(0)
+        }
+    }
+
+    impl embedded_io::blocking::Write for StubIo {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl embedded_io::blocking::Seek for StubIo {
+        fn seek(&mut self, _pos: embedded_io::SeekFrom) -> Result<u64, Self::Error> {
+            Ok(0)
+        }
+    }
+
+    impl PersistentStorage for StubStorage {
+        type Id = u32;
+        type Flags = ();
+        type Info = ();
+        type Error = Infallible;
+        type Io<'a> = StubIo;
+
+        fn create(&mut self, _objid: &Self::Id, _flags: &Self::Flags) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn destroy(&mut self, _objid: &Self::Id) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn get_info(&mut self, _objid: &Self::Id) -> Result<Self::Info, Self::Error> {
+            Ok(())
+        }
+
+        fn set_info(&mut self, _objid: &Self::Id, _info: Self::Info) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn read_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            self.last_handle_call = Some("read_handle");
+            Ok(StubIo)
+        }
+
+        fn write_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            self.last_handle_call = Some("write_handle");
+            Ok(StubIo)
+        }
+
+        fn rw_handle(&mut self, _objid: &Self::Id) -> Result<Self::Io<'_>, Self::Error> {
+            self.last_handle_call = Some("rw_handle");
+            Ok(StubIo)
+        }
+
+        fn truncate(&mut self, _objid: &Self::Id, _size: u64) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn persist_state(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn load_state(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn open_assigns_monotonically_increasing_handles() {
+        let mut table = ResourceTable::new(StubStorage {
+            last_handle_call: None,
+        });
+        let a = table.open(1, Mode::Read).unwrap();
+        let b = table.open(2, Mode::Read).unwrap();
+        assert!(b > a);
+    }
+
+    #[test]
+    fn open_fails_instead_of_wrapping_once_handles_are_exhausted() {
+        let mut table = ResourceTable::new(StubStorage {
+            last_handle_call: None,
+        });
+        table.next = u32::MAX as u64;
+        let last = table.open(1, Mode::Read).unwrap();
+        assert_eq!(last, u32::MAX);
+        assert!(matches!(
+            table.open(2, Mode::Read),
+            Err(Error::HandlesExhausted)
+        ));
+    }
+
+    #[test]
+    fn get_dispatches_read_write_handles_through_rw_handle() {
+        let mut table = ResourceTable::new(StubStorage {
+            last_handle_call: None,
+        });
+        let handle = table.open(1, Mode::ReadWrite).unwrap();
+
+        table.get(handle).unwrap();
+        assert_eq!(table.storage().last_handle_call, Some("rw_handle"));
+
+        table.get_mut(handle).unwrap();
+        assert_eq!(table.storage().last_handle_call, Some("rw_handle"));
+    }
+
+    #[test]
+    fn get_mut_rejects_a_read_only_handle() {
+        let mut table = ResourceTable::new(StubStorage {
+            last_handle_call: None,
+        });
+        let handle = table.open(1, Mode::Read).unwrap();
+        assert!(matches!(
+            table.get_mut(handle),
+            Err(Error::ModeViolation(_, Mode::Read))
+        ));
+    }
+
+    #[test]
+    fn close_forgets_the_handle() {
+        let mut table = ResourceTable::new(StubStorage {
+            last_handle_call: None,
+        });
+        let handle = table.open(1, Mode::Read).unwrap();
+        table.close(handle).unwrap();
+        assert!(matches!(table.get(handle), Err(Error::BadHandle(_))));
+    }
+}
@@ -0,0 +1,261 @@
+//! Bridges between `std::io` and `embedded_io` streams.
+
+use embedded_io::{
+    blocking::{Read as EioRead, Seek as EioSeek, Write as EioWrite},
+    Error as EioError, ErrorKind, Io, SeekFrom as EioSeekFrom,
+};
+use std::io::{Read as StdRead, Seek as StdSeek, SeekFrom as StdSeekFrom, Write as StdWrite};
+
+/// Wraps a `std::io::Error` so it can satisfy `embedded_io::Error`.
+#[derive(Debug)]
+pub struct StdIoError(pub std::io::Error);
+
+impl core::fmt::Display for StdIoError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for StdIoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl EioError for StdIoError {
+    fn kind(&self) -> ErrorKind {
+        // `embedded_io::ErrorKind` is `#[non_exhaustive]` with a single
+        // `Other` variant, so there is nowhere to put the richer
+        // `std::io::ErrorKind`; it survives in `self.0` instead, and the
+        // derived `Debug` impl above still shows it.
+        ErrorKind::Other
+    }
+}
+
+fn to_std_seek(pos: EioSeekFrom) -> StdSeekFrom {
+    match pos {
+        EioSeekFrom::Start(n) => StdSeekFrom::Start(n),
+        EioSeekFrom::End(n) => StdSeekFrom::End(n),
+        EioSeekFrom::Current(n) => StdSeekFrom::Current(n),
+    }
+}
+
+fn to_eio_seek(pos: StdSeekFrom) -> EioSeekFrom {
+    match pos {
+        StdSeekFrom::Start(n) => EioSeekFrom::Start(n),
+        StdSeekFrom::End(n) => EioSeekFrom::End(n),
+        StdSeekFrom::Current(n) => EioSeekFrom::Current(n),
+    }
+}
+
+/// Adapts a `std::io` stream to `embedded_io`'s blocking `Read`/`Write`/`Seek` traits.
+#[derive(Debug, Clone)]
+pub struct StdIo<T>(pub T);
+
+impl<T> StdIo<T> {
+    /// Wraps `inner`.
+    pub fn new(inner: T) -> Self {
+        Self(inner)
+    }
+
+    /// Returns a reference to the wrapped value.
+    pub fn get_ref(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the wrapped value.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    /// Unwraps this adapter, returning the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Io for StdIo<T> {
+    type Error = StdIoError;
+}
+
+impl<T: StdRead> EioRead for StdIo<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.0.read(buf).map_err(StdIoError)
+    }
+}
+
+impl<T: StdWrite> EioWrite for StdIo<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.0.write(buf).map_err(StdIoError)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.flush().map_err(StdIoError)
+    }
+}
+
+impl<T: StdSeek> EioSeek for StdIo<T> {
+    fn seek(&mut self, pos: EioSeekFrom) -> Result<u64, Self::Error> {
+        self.0.seek(to_std_seek(pos)).map_err(StdIoError)
+    }
+}
+
+/// Adapts an `embedded_io` stream to `std::io`'s `Read`/`Write`/`Seek` traits.
+#[derive(Debug, Clone)]
+pub struct EmbeddedIo<T>(pub T);
+
+impl<T> EmbeddedIo<T> {
+    /// Wraps `inner`.
+    pub fn new(inner: T) -> Self {
+        Self(inner)
+    }
+
+    /// Returns a reference to the wrapped value.
+    pub fn get_ref(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the wrapped value.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    /// Unwraps this adapter, returning the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+fn to_std_io_error<E: EioError>(err: E) -> std::io::Error {
+    // `embedded_io::ErrorKind` only ever reports `Other`, so there is no
+    // finer-grained `std::io::ErrorKind` to recover here; the original
+    // error's `Debug` output is kept in the message instead of being
+    // discarded.
+    std::io::Error::other(format!("{:?}", err))
+}
+
+impl<T: EioRead> StdRead for EmbeddedIo<T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf).map_err(to_std_io_error)
+    }
+}
+
+impl<T: EioWrite> StdWrite for EmbeddedIo<T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf).map_err(to_std_io_error)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush().map_err(to_std_io_error)
+    }
+}
+
+impl<T: EioSeek> StdSeek for EmbeddedIo<T> {
+    fn seek(&mut self, pos: StdSeekFrom) -> std::io::Result<u64> {
+        self.0.seek(to_eio_seek(pos)).map_err(to_std_io_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn std_io_round_trips_write_seek_read() {
+        let mut io = StdIo::new(Cursor::new(Vec::new()));
+        EioWrite::write(&mut io, b"hello world").unwrap();
+        EioSeek::seek(&mut io, EioSeekFrom::Start(0)).unwrap();
+        let mut buf = [0u8; 11];
+        EioRead::read(&mut io, &mut buf).unwrap();
+        assert_eq!(&buf, b"hello world");
+    }
+
+    #[test]
+    fn std_io_error_kind_is_always_other_but_debug_keeps_the_original_kind() {
+        let not_found = StdIoError(std::io::Error::new(std::io::ErrorKind::NotFound, "x"));
+        // `embedded_io::ErrorKind` has no variant to carry this in.
+        assert_eq!(not_found.kind(), ErrorKind::Other);
+        // The original `std::io::ErrorKind` still shows up in `Debug`.
+        assert!(format!("{not_found:?}").contains("NotFound"));
+    }
+
+    /// A minimal in-memory `embedded_io` stream for exercising `EmbeddedIo`.
+    struct StubIo {
+        data: Vec<u8>,
+        pos: usize,
+    }
+
+    impl Io for StubIo {
+        type Error = StubError;
+    }
+
+    #[derive(Debug)]
+    struct StubError(ErrorKind);
+
+    impl EioError for StubError {
+        fn kind(&self) -> ErrorKind {
+            self.0
+        }
+    }
+
+    impl EioRead for StubIo {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let available = self.data.len().saturating_sub(self.pos);
+            let n = core::cmp::min(buf.len(), available);
+            buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+            self.pos += n;
+            Ok(n)
+        }
+    }
+
+    impl EioWrite for StubIo {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            let end = self.pos + buf.len();
+            if end > self.data.len() {
+                self.data.resize(end, 0);
+            }
+            self.data[self.pos..end].copy_from_slice(buf);
+            self.pos = end;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl EioSeek for StubIo {
+        fn seek(&mut self, pos: EioSeekFrom) -> Result<u64, Self::Error> {
+            self.pos = match pos {
+                EioSeekFrom::Start(n) => n as usize,
+                EioSeekFrom::End(n) => (self.data.len() as i64 + n) as usize,
+                EioSeekFrom::Current(n) => (self.pos as i64 + n) as usize,
+            };
+            Ok(self.pos as u64)
+        }
+    }
+
+    #[test]
+    fn embedded_io_round_trips_write_seek_read() {
+        let mut io = EmbeddedIo::new(StubIo {
+            data: Vec::new(),
+            pos: 0,
+        });
+        StdWrite::write(&mut io, b"hello world").unwrap();
+        StdSeek::seek(&mut io, StdSeekFrom::Start(0)).unwrap();
+        let mut buf = [0u8; 11];
+        StdRead::read(&mut io, &mut buf).unwrap();
+        assert_eq!(&buf, b"hello world");
+    }
+
+    #[test]
+    fn embedded_io_error_maps_into_std_io_other_but_keeps_the_message() {
+        // `embedded_io::ErrorKind` only ever reports `Other`, so every
+        // `embedded_io` error becomes `std::io::ErrorKind::Other`; the
+        // original error's `Debug` output survives in the message.
+        let err = to_std_io_error(StubError(ErrorKind::Other));
+        assert_eq!(err.kind(), std::io::ErrorKind::Other);
+        assert!(format!("{err}").contains("StubError"));
+    }
+}